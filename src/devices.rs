@@ -1,14 +1,8 @@
 // #[cfg_attr(docsrs, doc(cfg(feature = "sevensegment")))]
 #[allow(unused_imports)]
-use crate::{Error, IS31FL3729};
+use crate::{Address, DelayBus, Error, I2cBus, IS31FL3729};
 #[allow(unused_imports)]
 use core::convert::TryFrom;
-#[allow(unused_imports)]
-use embedded_hal::blocking::delay::DelayMs;
-#[allow(unused_imports)]
-use embedded_hal::blocking::i2c::Write;
-#[allow(unused_imports)]
-use embedded_hal::blocking::i2c::WriteRead;
 
 #[cfg(feature = "sevensegment")]
 pub struct SevenSegment<I2C> {
@@ -18,8 +12,7 @@ pub struct SevenSegment<I2C> {
 #[cfg(feature = "sevensegment")]
 impl<I2C, I2cError> SevenSegment<I2C>
 where
-    I2C: Write<Error = I2cError>,
-    I2C: WriteRead<Error = I2cError>,
+    I2C: I2cBus<Error = I2cError>,
 {
     pub fn unwrap(self) -> I2C {
         self.device.i2c
@@ -31,16 +24,14 @@ where
 
     pub fn configure(i2c: I2C) -> SevenSegment<I2C> {
         SevenSegment {
-            device: IS31FL3729 {
+            device: IS31FL3729::new(
                 i2c,
-                address: 0x34,
+                Address::Gnd,
                 // logically there are 9 "columns" of 7-segment displays
-                width: 9,
-                height: 8, // 7 segments plus a decimal point
-                calc_pixel: |x: u8, y: u8| -> u8 {
-                    x + (0x10 * y)
-                },
-            },
+                9,
+                8, // 7 segments plus a decimal point
+                |x: u8, y: u8| -> u8 { x + (0x10 * y) },
+            ),
         }
     }
 
@@ -152,7 +143,7 @@ where
         Ok(())
     }
 
-    pub fn setup<DEL: DelayMs<u8>>(&mut self, delay: &mut DEL) -> Result<(), Error<I2cError>> {
+    pub fn setup<DEL: DelayBus>(&mut self, delay: &mut DEL) -> Result<(), Error<I2cError>> {
         self.device.setup(delay)
     }
 }
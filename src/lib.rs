@@ -3,17 +3,100 @@
 /// Preconfigured devices
 pub mod devices;
 
+#[cfg(not(feature = "eh1"))]
 use embedded_hal::blocking::delay::DelayMs;
+#[cfg(not(feature = "eh1"))]
 use embedded_hal::blocking::i2c::Write;
+#[cfg(not(feature = "eh1"))]
 use embedded_hal::blocking::i2c::WriteRead;
 
+/// Minimal blocking I2C abstraction used internally so the driver logic is shared
+/// across embedded-hal versions. The default impl is built on the `embedded-hal`
+/// 0.2 `Write`/`WriteRead` traits; enabling the `eh1` feature swaps in an impl over
+/// the unified `embedded-hal` 1.0 `I2c` trait instead.
+pub trait I2cBus {
+    /// The bus error type surfaced by the underlying HAL.
+    type Error;
+    /// Write `bytes` to the device at `address`.
+    fn bus_write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error>;
+    /// Write `bytes` to the device at `address`, then read the response into `buffer`.
+    fn bus_write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error>;
+}
+
+#[cfg(not(feature = "eh1"))]
+impl<I2C, E> I2cBus for I2C
+where
+    I2C: Write<Error = E>,
+    I2C: WriteRead<Error = E>,
+{
+    type Error = E;
+    fn bus_write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        Write::write(self, address, bytes)
+    }
+    fn bus_write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        WriteRead::write_read(self, address, bytes, buffer)
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<I2C, E> I2cBus for I2C
+where
+    I2C: eh1::i2c::I2c<Error = E>,
+{
+    type Error = E;
+    fn bus_write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        eh1::i2c::I2c::write(self, address, bytes)
+    }
+    fn bus_write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        eh1::i2c::I2c::write_read(self, address, bytes, buffer)
+    }
+}
+
+/// Minimal blocking millisecond delay, abstracted over embedded-hal versions in the
+/// same way as [I2cBus] so [setup](IS31FL3729::setup)/[reset](IS31FL3729::reset) work
+/// under both the 0.2 `DelayMs` trait and the 1.0 `DelayNs` trait.
+pub trait DelayBus {
+    /// Block for at least `ms` milliseconds.
+    fn delay_ms(&mut self, ms: u8);
+}
+
+#[cfg(not(feature = "eh1"))]
+impl<D: DelayMs<u8>> DelayBus for D {
+    fn delay_ms(&mut self, ms: u8) {
+        DelayMs::delay_ms(self, ms)
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<D: eh1::delay::DelayNs> DelayBus for D {
+    fn delay_ms(&mut self, ms: u8) {
+        eh1::delay::DelayNs::delay_ms(self, ms as u32)
+    }
+}
+
 /// A struct to integrate with a new IS31FL3729 powered device.
 pub struct IS31FL3729<I2C> {
     /// The i2c bus that is used to interact with the device. See implementation below for the
     /// trait methods required.
     pub i2c: I2C,
     /// The 7-bit i2c slave address of the device. By default on most devices this is `0x34`.
-    pub address: u8,
+    /// Private so it can only be set through [Address]; read it with [address](IS31FL3729::address).
+    address: u8,
     /// Width of the LED matrix
     pub width: u8,
     /// Height of the LED matrix
@@ -21,30 +104,68 @@ pub struct IS31FL3729<I2C> {
     /// Method to convert an x,y coordinate pair to a binary address that can be accessed using the
     /// bus.
     pub calc_pixel: fn(x: u8, y: u8) -> u8,
+    /// Gamma lookup applied to the perceptual 0 to 255 levels passed to [pixel](IS31FL3729::pixel),
+    /// [fill](IS31FL3729::fill) and [fill_matrix](IS31FL3729::fill_matrix) before they reach the
+    /// PWM registers. Defaults to [DEFAULT_GAMMA]; override with [set_gamma](IS31FL3729::set_gamma).
+    pub gamma: &'static [u8; 256],
+    /// In-RAM shadow of the PWM registers, kept so the [graphics](crate#features) `DrawTarget`
+    /// can batch pixel writes and push them in one [flush](IS31FL3729::flush).
+    #[cfg(feature = "graphics")]
+    shadow: [u8; addresses::PWM_LEN],
 }
 
 impl<I2C, I2cError> IS31FL3729<I2C>
 where
-    I2C: Write<Error = I2cError>,
-    I2C: WriteRead<Error = I2cError>,
+    I2C: I2cBus<Error = I2cError>,
 {
-    /// Fill all pixels of the display at once. The brightness should range from 0 to 255.
-    pub fn fill_matrix(&mut self, brightnesses: &[u8]) -> Result<(), I2cError> {
-        // Extend by one, to add address to the beginning
-        let mut buf = [0x00; 1+addresses::PWM_LEN];
-        buf[0] = addresses::PWM_BASE_REGISTER; // set the initial address
+    /// Construct a driver for a device whose AD pin is strapped to the given [Address]. The
+    /// resolved 7-bit address is one the hardware is guaranteed to respond to; the address can
+    /// only be changed afterwards through [set_address](Self::set_address).
+    pub fn new(
+        i2c: I2C,
+        address: Address,
+        width: u8,
+        height: u8,
+        calc_pixel: fn(x: u8, y: u8) -> u8,
+    ) -> Self {
+        Self {
+            i2c,
+            address: address.address(),
+            width,
+            height,
+            calc_pixel,
+            gamma: &DEFAULT_GAMMA,
+            #[cfg(feature = "graphics")]
+            shadow: [0x00; addresses::PWM_LEN],
+        }
+    }
 
-        buf[1..=addresses::PWM_LEN].copy_from_slice(&brightnesses[..=(addresses::PWM_LEN-1)]);
-        self.write(&buf)?;
+    /// Fill all pixels of the display at once. The brightnesses are perceptual 0 to 255 levels,
+    /// mapped through the [gamma](Self::gamma) table before being written.
+    pub fn fill_matrix(&mut self, brightnesses: &[u8]) -> Result<(), I2cError> {
+        let mut mapped = [0x00; addresses::PWM_LEN];
+        for (dst, level) in mapped.iter_mut().zip(&brightnesses[..addresses::PWM_LEN]) {
+            *dst = self.gamma[*level as usize];
+        }
+        self.fill_matrix_raw(&mapped)
+    }
 
+    /// Fill all pixels of the display at once with raw PWM values, bypassing the
+    /// [gamma](Self::gamma) table.
+    pub fn fill_matrix_raw(&mut self, brightnesses: &[u8]) -> Result<(), I2cError> {
+        self.write(&fill_matrix_buf(brightnesses))?;
         Ok(())
     }
 
-    /// Fill the display with a single brightness. The brightness should range from 0 to 255.
+    /// Fill the display with a single brightness. The brightness is a perceptual 0 to 255 level,
+    /// mapped through the [gamma](Self::gamma) table before being written.
     pub fn fill(&mut self, brightness: u8) -> Result<(), I2cError> {
-        let mut buf = [brightness; addresses::PWM_LEN+1];
-        buf[0] = addresses::PWM_BASE_REGISTER; // set the initial address
-        self.write(&buf)?;
+        self.fill_raw(self.gamma[brightness as usize])
+    }
+
+    /// Fill the display with a single raw PWM value, bypassing the [gamma](Self::gamma) table.
+    pub fn fill_raw(&mut self, brightness: u8) -> Result<(), I2cError> {
+        self.write(&fill_buf(brightness))?;
         Ok(())
     }
 
@@ -57,7 +178,7 @@ where
     /// 2. The chip will be put in shutdown mode
     /// 3. The chip will be configured to use the maximum voltage
     /// 4. The chip will be taken out of shutdown mode
-    pub fn setup<DEL: DelayMs<u8>>(&mut self, delay: &mut DEL) -> Result<(), Error<I2cError>> {
+    pub fn setup<DEL: DelayBus>(&mut self, delay: &mut DEL) -> Result<(), Error<I2cError>> {
         self.reset(delay)?;
         self.shutdown(true)?;
         delay.delay_ms(10);
@@ -72,9 +193,16 @@ where
     }
 
     /// Set the brightness at a specific x,y coordinate. Just like the [fill method](Self::fill)
-    /// the brightness should range from 0 to 255. If the coordinate is out of range then the
-    /// function will return an error of [InvalidLocation](Error::InvalidLocation).
+    /// the brightness is a perceptual 0 to 255 level, mapped through the [gamma](Self::gamma) table
+    /// before being written. If the coordinate is out of range then the function will return an
+    /// error of [InvalidLocation](Error::InvalidLocation).
     pub fn pixel(&mut self, x: u8, y: u8, brightness: u8) -> Result<(), Error<I2cError>> {
+        self.pixel_raw(x, y, self.gamma[brightness as usize])
+    }
+
+    /// Set the raw PWM value at a specific x,y coordinate, bypassing the [gamma](Self::gamma)
+    /// table. Returns [InvalidLocation](Error::InvalidLocation) if the coordinate is out of range.
+    pub fn pixel_raw(&mut self, x: u8, y: u8, brightness: u8) -> Result<(), Error<I2cError>> {
         if x > self.width {
             return Err(Error::InvalidLocation(x));
         }
@@ -89,16 +217,27 @@ where
         Ok(())
     }
 
-    /// Change the slave address to a new 7-bit address. Should be configured before calling
-    /// [setup](Self::setup) method.
-    pub fn set_address(&mut self, address: u8) {
-        self.address = address;
+    /// Change the slave address according to how the AD pin is strapped. Should be configured
+    /// before calling [setup](Self::setup) method.
+    pub fn set_address(&mut self, address: Address) {
+        self.address = address.address();
+    }
+
+    /// The 7-bit slave address the driver currently talks to.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Override the gamma table applied by [pixel](Self::pixel), [fill](Self::fill) and
+    /// [fill_matrix](Self::fill_matrix). Pass [DEFAULT_GAMMA] to restore the built-in curve.
+    pub fn set_gamma(&mut self, gamma: &'static [u8; 256]) {
+        self.gamma = gamma;
     }
 
     /// Send a reset message to the slave device. Delay is something that your device's HAL should
     /// provide which allows for the process to sleep for a certain amount of time (in this case 10
     /// MS to perform a reset).
-    pub fn reset<DEL: DelayMs<u8>>(&mut self, delay: &mut DEL) -> Result<(), I2cError> {
+    pub fn reset<DEL: DelayBus>(&mut self, delay: &mut DEL) -> Result<(), I2cError> {
         self.write_u8(addresses::RESET_REGISTER, addresses::RESET)?;
         delay.delay_ms(10);
         Ok(())
@@ -137,6 +276,16 @@ where
         self.write_u8(addresses::PWM_FREQ_REGISTER, pwm as u8)
     }
 
+    /// Configure the SWy pull-up and CSx pull-down resistors used to suppress
+    /// ghosting/crosstalk on large matrices. Larger resistances bleed more charge
+    /// between scans; dial them up if you see faint bleed on unaddressed LEDs.
+    pub fn set_deghost(&mut self, sw_pullup: Resistor, cs_pulldown: Resistor) -> Result<(), I2cError> {
+        self.write_u8(
+            addresses::PULL_DOWN_UP_REGISTER,
+            ((sw_pullup as u8) << 4) | (cs_pulldown as u8),
+        )
+    }
+
     /// Set the spread spectrum properties
     pub fn set_spread_spectrum(&mut self, enable: bool, range: SspRange, cycle: SspCycleTime) -> Result<(), I2cError> {
         self.write_u8(addresses::SPREAD_SPECTRUM_REGISTER,
@@ -153,6 +302,32 @@ where
     pub fn check_shorts(&mut self) -> Result<[u8; 18], I2cError> {
         self.check_open_short(false)
     }
+
+    /// Check for opens, returning a decoded [LedFaults] instead of raw bytes.
+    pub fn check_opens_faults(&mut self) -> Result<LedFaults, I2cError> {
+        Ok(LedFaults::new(self.check_open_short(true)?))
+    }
+    /// Check for shorts, returning a decoded [LedFaults] instead of raw bytes.
+    pub fn check_shorts_faults(&mut self) -> Result<LedFaults, I2cError> {
+        Ok(LedFaults::new(self.check_open_short(false)?))
+    }
+
+    /// Reverse [calc_pixel](Self::calc_pixel) for a CS/SW intersection back into the
+    /// user's x/y space, where the mapping is invertible. A fault at `(cs, sw)` belongs
+    /// to PWM offset `sw * 0x10 + cs` (the value `calc_pixel` returns, relative to
+    /// [PWM_BASE_REGISTER](addresses::PWM_BASE_REGISTER)); this searches the configured matrix for the
+    /// first `(x, y)` that maps there, returning `None` when nothing does.
+    pub fn reverse_pixel(&self, cs: u8, sw: u8) -> Option<(u8, u8)> {
+        let register = sw.wrapping_mul(0x10).wrapping_add(cs);
+        for y in 0..=self.height {
+            for x in 0..=self.width {
+                if (self.calc_pixel)(x, y) == register {
+                    return Some((x, y));
+                }
+            }
+        }
+        None
+    }
     fn check_open_short(&mut self, open: bool) -> Result<[u8; 18], I2cError> {
         let mut buf = [0x00 ; addresses::OPEN_SHORT_LEN];
         let old_config = self.read_u8(addresses::CONFIG_REGISTER)?;
@@ -161,7 +336,7 @@ where
         self.write_u8(addresses::GCC_REGISTER, 0x01)?;
         self.write_u8(addresses::CONFIG_REGISTER,
                       (old_config & 0xF9) | ((osde as u8) << 1))?;
-        self.i2c.write_read(
+        self.i2c.bus_write_read(
             self.address,
             &[addresses::OPEN_SHORT_BASE_REGISTER],
             &mut buf)?;
@@ -171,20 +346,356 @@ where
     }
 
     fn write(&mut self, buf: &[u8]) -> Result<(), I2cError> {
-        self.i2c.write(self.address, buf)
+        self.i2c.bus_write(self.address, buf)
     }
 
     fn write_u8(&mut self, register: u8, value: u8) -> Result<(), I2cError> {
-        self.i2c.write(self.address, &[register, value])
+        self.i2c.bus_write(self.address, &[register, value])
     }
 
     fn read_u8(&mut self, register: u8) -> Result<u8, I2cError> {
         let mut buf = [0x00];
-        self.i2c.write_read(self.address, &[register], &mut buf)?;
+        self.i2c.bus_write_read(self.address, &[register], &mut buf)?;
         Ok(buf[0])
     }
 }
 
+/// Build the register-prefixed buffer for a full-matrix PWM write, as consumed by
+/// [fill_matrix](IS31FL3729::fill_matrix) and its async counterpart.
+fn fill_matrix_buf(brightnesses: &[u8]) -> [u8; addresses::PWM_LEN + 1] {
+    // Extend by one, to add address to the beginning
+    let mut buf = [0x00; 1 + addresses::PWM_LEN];
+    buf[0] = addresses::PWM_BASE_REGISTER; // set the initial address
+    buf[1..=addresses::PWM_LEN].copy_from_slice(&brightnesses[..=(addresses::PWM_LEN - 1)]);
+    buf
+}
+
+/// Build the register-prefixed buffer for a single-brightness [fill](IS31FL3729::fill).
+fn fill_buf(brightness: u8) -> [u8; addresses::PWM_LEN + 1] {
+    let mut buf = [brightness; addresses::PWM_LEN + 1];
+    buf[0] = addresses::PWM_BASE_REGISTER; // set the initial address
+    buf
+}
+
+/// A decoded open/short report: one bit per LED across the full 16 CS x 9 SW grid, as
+/// produced by [check_opens_faults](IS31FL3729::check_opens_faults) and
+/// [check_shorts_faults](IS31FL3729::check_shorts_faults). Byte `sw * 2 + (cs >> 3)` holds
+/// the bits for SW row `sw`, and bit `cs & 7` is the fault flag for that intersection.
+pub struct LedFaults {
+    raw: [u8; addresses::OPEN_SHORT_LEN],
+}
+
+impl LedFaults {
+    /// Number of CS (current-sink) columns covered by the report.
+    pub const CS_COUNT: u8 = 16;
+    /// Number of SW (switch) rows covered by the report.
+    pub const SW_COUNT: u8 = 9;
+
+    /// Wrap a raw 18-byte open/short report.
+    pub fn new(raw: [u8; addresses::OPEN_SHORT_LEN]) -> Self {
+        Self { raw }
+    }
+
+    /// The underlying raw bytes exactly as returned by the chip.
+    pub fn as_bytes(&self) -> &[u8; addresses::OPEN_SHORT_LEN] {
+        &self.raw
+    }
+
+    /// Whether the LED at the given CS/SW intersection is flagged faulty. Coordinates
+    /// outside the grid are reported as not faulty.
+    pub fn is_faulty(&self, cs: u8, sw: u8) -> bool {
+        if cs >= Self::CS_COUNT || sw >= Self::SW_COUNT {
+            return false;
+        }
+        let byte = (sw * 2 + (cs >> 3)) as usize;
+        self.raw[byte] & (1 << (cs & 7)) != 0
+    }
+
+    /// Iterate over the `(cs, sw)` coordinates of every faulty LED, row by row.
+    pub fn iter(&self) -> FaultyLeds<'_> {
+        FaultyLeds {
+            faults: self,
+            cs: 0,
+            sw: 0,
+        }
+    }
+}
+
+/// Iterator over the `(cs, sw)` coordinates of faulty LEDs in a [LedFaults] report.
+pub struct FaultyLeds<'a> {
+    faults: &'a LedFaults,
+    cs: u8,
+    sw: u8,
+}
+
+impl Iterator for FaultyLeds<'_> {
+    type Item = (u8, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.sw < LedFaults::SW_COUNT {
+            let (cs, sw) = (self.cs, self.sw);
+            self.cs += 1;
+            if self.cs >= LedFaults::CS_COUNT {
+                self.cs = 0;
+                self.sw += 1;
+            }
+            if self.faults.is_faulty(cs, sw) {
+                return Some((cs, sw));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod ledfaults_tests {
+    use super::{addresses, LedFaults};
+
+    // Build a report with three known LEDs flagged:
+    //   (cs=0,  sw=0) -> byte 0,  bit 0  -> 0x01
+    //   (cs=9,  sw=0) -> byte 1,  bit 1  -> 0x02
+    //   (cs=15, sw=8) -> byte 17, bit 7  -> 0x80
+    fn sample() -> LedFaults {
+        let mut raw = [0x00; addresses::OPEN_SHORT_LEN];
+        raw[0] = 0x01;
+        raw[1] = 0x02;
+        raw[17] = 0x80;
+        LedFaults::new(raw)
+    }
+
+    #[test]
+    fn is_faulty_decodes_known_bits() {
+        let faults = sample();
+        assert!(faults.is_faulty(0, 0));
+        assert!(faults.is_faulty(9, 0));
+        assert!(faults.is_faulty(15, 8));
+        // Neighbours of the flagged bits must stay clear.
+        assert!(!faults.is_faulty(1, 0));
+        assert!(!faults.is_faulty(8, 0));
+        assert!(!faults.is_faulty(0, 1));
+        assert!(!faults.is_faulty(14, 8));
+    }
+
+    #[test]
+    fn is_faulty_rejects_out_of_grid() {
+        let faults = sample();
+        assert!(!faults.is_faulty(LedFaults::CS_COUNT, 0));
+        assert!(!faults.is_faulty(0, LedFaults::SW_COUNT));
+    }
+
+    #[test]
+    fn iter_yields_coordinates_row_by_row() {
+        let faults = sample();
+        let mut it = faults.iter();
+        assert_eq!(it.next(), Some((0, 0)));
+        assert_eq!(it.next(), Some((9, 0)));
+        assert_eq!(it.next(), Some((15, 8)));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn iter_is_empty_for_clean_report() {
+        let faults = LedFaults::new([0x00; addresses::OPEN_SHORT_LEN]);
+        assert_eq!(faults.iter().next(), None);
+    }
+}
+
+/// Async full-matrix updates for DMA-backed I2C buses.
+///
+/// These mirror the blocking [fill_matrix](IS31FL3729::fill_matrix) and
+/// [fill](IS31FL3729::fill) but drive the transfer through
+/// `embedded-hal-async`'s `I2c`, letting the executor run other work while the
+/// ~144-byte frame is pushed over a DMA-driven bus. The blocking API is
+/// unaffected.
+#[cfg(feature = "async")]
+impl<I2C, I2cError> IS31FL3729<I2C>
+where
+    I2C: embedded_hal_async::i2c::I2c<Error = I2cError>,
+{
+    /// Fill all pixels of the display at once, without blocking the CPU for the
+    /// whole transfer. The brightnesses are perceptual 0 to 255 levels, mapped through
+    /// the [gamma](Self::gamma) table before being written.
+    pub async fn fill_matrix_async(&mut self, brightnesses: &[u8]) -> Result<(), I2cError> {
+        let mut mapped = [0x00; addresses::PWM_LEN];
+        for (dst, level) in mapped.iter_mut().zip(&brightnesses[..addresses::PWM_LEN]) {
+            *dst = self.gamma[*level as usize];
+        }
+        self.i2c
+            .write(self.address, &fill_matrix_buf(&mapped))
+            .await?;
+        Ok(())
+    }
+
+    /// Fill the display with a single brightness, without blocking the CPU for the
+    /// whole transfer. The brightness is a perceptual 0 to 255 level, mapped through
+    /// the [gamma](Self::gamma) table before being written.
+    pub async fn fill_async(&mut self, brightness: u8) -> Result<(), I2cError> {
+        let level = self.gamma[brightness as usize];
+        self.i2c.write(self.address, &fill_buf(level)).await?;
+        Ok(())
+    }
+}
+
+/// Expose the LED matrix as an [embedded-graphics](embedded_graphics_core) `DrawTarget` with
+/// [Gray8](embedded_graphics_core::pixelcolor::Gray8) color. Pixel writes land in the in-RAM
+/// [shadow](IS31FL3729#structfield.shadow) buffer, mapped through [calc_pixel](IS31FL3729#structfield.calc_pixel),
+/// and are pushed to the device in a single [fill_matrix_raw](IS31FL3729::fill_matrix_raw)-style
+/// transfer on [flush](IS31FL3729::flush), so a whole frame of shapes or text costs one I2C
+/// transaction. Luma values are written as raw PWM values; the [gamma](IS31FL3729#structfield.gamma)
+/// table is not applied.
+#[cfg(feature = "graphics")]
+mod graphics {
+    use super::{addresses, IS31FL3729, I2cBus};
+    use embedded_graphics_core::draw_target::DrawTarget;
+    use embedded_graphics_core::geometry::{OriginDimensions, Size};
+    use embedded_graphics_core::pixelcolor::{Gray8, GrayColor};
+    use embedded_graphics_core::Pixel;
+
+    impl<I2C, I2cError> IS31FL3729<I2C>
+    where
+        I2C: I2cBus<Error = I2cError>,
+    {
+        /// Push the shadow framebuffer to the device in a single transfer.
+        pub fn flush(&mut self) -> Result<(), I2cError> {
+            let buf = super::fill_matrix_buf(&self.shadow);
+            self.write(&buf)
+        }
+    }
+
+    impl<I2C> OriginDimensions for IS31FL3729<I2C> {
+        fn size(&self) -> Size {
+            Size::new(self.width as u32, self.height as u32)
+        }
+    }
+
+    impl<I2C, I2cError> DrawTarget for IS31FL3729<I2C>
+    where
+        I2C: I2cBus<Error = I2cError>,
+    {
+        type Color = Gray8;
+        type Error = I2cError;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            for Pixel(coord, color) in pixels {
+                if coord.x < 0
+                    || coord.y < 0
+                    || coord.x >= self.width as i32
+                    || coord.y >= self.height as i32
+                {
+                    continue;
+                }
+                let register = (self.calc_pixel)(coord.x as u8, coord.y as u8) as usize;
+                if register < addresses::PWM_LEN {
+                    self.shadow[register] = color.luma();
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::super::{Address, IS31FL3729};
+        use embedded_graphics_core::draw_target::DrawTarget;
+        use embedded_graphics_core::geometry::Point;
+        use embedded_graphics_core::pixelcolor::Gray8;
+        use embedded_graphics_core::Pixel;
+
+        struct NullBus;
+        impl embedded_hal::blocking::i2c::Write for NullBus {
+            type Error = ();
+            fn write(&mut self, _addr: u8, _bytes: &[u8]) -> Result<(), ()> {
+                Ok(())
+            }
+        }
+        impl embedded_hal::blocking::i2c::WriteRead for NullBus {
+            type Error = ();
+            fn write_read(&mut self, _addr: u8, _bytes: &[u8], _buf: &mut [u8]) -> Result<(), ()> {
+                Ok(())
+            }
+        }
+
+        fn target() -> IS31FL3729<NullBus> {
+            // width 9, height 8: valid x in 0..9, y in 0..8
+            IS31FL3729::new(NullBus, Address::Gnd, 9, 8, |x, y| x + 0x10 * y)
+        }
+
+        #[test]
+        fn draw_iter_clips_out_of_bounds() {
+            let mut dev = target();
+            let rejected = [
+                Point::new(-1, 0),
+                Point::new(0, -1),
+                Point::new(9, 0),   // == width
+                Point::new(0, 8),   // == height
+                Point::new(256, 0), // would wrap to 0 if cast to u8 before the check
+                Point::new(0, 256),
+            ];
+            dev.draw_iter(rejected.iter().map(|p| Pixel(*p, Gray8::new(0xFF))))
+                .unwrap();
+            assert!(
+                dev.shadow.iter().all(|&b| b == 0),
+                "rejected pixels must not touch the shadow buffer"
+            );
+        }
+
+        #[test]
+        fn draw_iter_writes_in_bounds_pixel() {
+            let mut dev = target();
+            dev.draw_iter(core::iter::once(Pixel(Point::new(1, 1), Gray8::new(0x7F))))
+                .unwrap();
+            let register = (1 + 0x10 * 1) as usize;
+            assert_eq!(dev.shadow[register], 0x7F);
+        }
+    }
+}
+
+/// Default gamma table mapping a perceptual 0 to 255 level to a PWM value, computed as
+/// `round(255 * (in / 255)^2.2)`. Applied by [pixel](IS31FL3729::pixel), [fill](IS31FL3729::fill)
+/// and [fill_matrix](IS31FL3729::fill_matrix) unless overridden with
+/// [set_gamma](IS31FL3729::set_gamma).
+pub const DEFAULT_GAMMA: [u8; 256] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3,
+    3, 4, 4, 4, 4, 5, 5, 5, 5, 6, 6, 6,
+    6, 7, 7, 7, 8, 8, 8, 9, 9, 9, 10, 10,
+    11, 11, 11, 12, 12, 13, 13, 13, 14, 14, 15, 15,
+    16, 16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 22,
+    22, 23, 23, 24, 25, 25, 26, 26, 27, 28, 28, 29,
+    30, 30, 31, 32, 33, 33, 34, 35, 35, 36, 37, 38,
+    39, 39, 40, 41, 42, 43, 43, 44, 45, 46, 47, 48,
+    49, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59,
+    60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71,
+    73, 74, 75, 76, 77, 78, 79, 81, 82, 83, 84, 85,
+    87, 88, 89, 90, 91, 93, 94, 95, 97, 98, 99, 100,
+    102, 103, 105, 106, 107, 109, 110, 111, 113, 114, 116, 117,
+    119, 120, 121, 123, 124, 126, 127, 129, 130, 132, 133, 135,
+    137, 138, 140, 141, 143, 145, 146, 148, 149, 151, 153, 154,
+    156, 158, 159, 161, 163, 165, 166, 168, 170, 172, 173, 175,
+    177, 179, 181, 182, 184, 186, 188, 190, 192, 194, 196, 197,
+    199, 201, 203, 205, 207, 209, 211, 213, 215, 217, 219, 221,
+    223, 225, 227, 229, 231, 234, 236, 238, 240, 242, 244, 246,
+    248, 251, 253, 255,
+];
+
+#[cfg(test)]
+mod gamma_tests {
+    extern crate std;
+    use super::DEFAULT_GAMMA;
+
+    #[test]
+    fn default_gamma_matches_formula() {
+        for i in 0..=255usize {
+            let expected = (255.0_f64 * (i as f64 / 255.0).powf(2.2)).round() as u8;
+            assert_eq!(DEFAULT_GAMMA[i], expected, "gamma[{}] off", i);
+        }
+    }
+}
+
 /// See the [data sheet](https://lumissil.com/assets/pdf/core/IS31FL3729_DS.pdf)
 /// for more information on registers.
 pub mod addresses {
@@ -222,6 +733,38 @@ impl<E> From<E> for Error<E> {
     }
 }
 
+/// Slave address selection, set by how the AD pin is tied on the board. Each variant names the
+/// strapping option and resolves to the 7-bit address the chip responds to via [address](Self::address).
+#[derive(Clone, Copy, Debug)]
+pub enum Address {
+    /// AD tied to GND. This is the power-on default (`0x34`).
+    Gnd,
+    /// AD tied to VCC.
+    Vcc,
+    /// AD tied to SCL.
+    Scl,
+    /// AD tied to SDA.
+    Sda,
+}
+
+impl Address {
+    /// The 7-bit slave address for this strapping.
+    pub fn address(self) -> u8 {
+        match self {
+            Address::Gnd => 0x34,
+            Address::Vcc => 0x35,
+            Address::Scl => 0x36,
+            Address::Sda => 0x37,
+        }
+    }
+}
+
+impl Default for Address {
+    fn default() -> Self {
+        Address::Gnd
+    }
+}
+
 #[repr(u8)]
 pub enum PwmFreq {
     /// 55kHz
@@ -242,6 +785,28 @@ pub enum PwmFreq {
     P80k = 0b111,
 }
 
+/// Selectable pull-up (SWy) / pull-down (CSx) resistor value, as programmed into the two
+/// nibbles of [PULL_DOWN_UP_REGISTER](addresses::PULL_DOWN_UP_REGISTER) for deghosting.
+#[repr(u8)]
+pub enum Resistor {
+    /// No resistor
+    None = 0b000,
+    /// 0.5k ohm
+    R0k5 = 0b001,
+    /// 1k ohm
+    R1k = 0b010,
+    /// 2k ohm
+    R2k = 0b011,
+    /// 4k ohm
+    R4k = 0b100,
+    /// 8k ohm
+    R8k = 0b101,
+    /// 16k ohm
+    R16k = 0b110,
+    /// 32k ohm
+    R32k = 0b111,
+}
+
 #[repr(u8)]
 pub enum SwSetting {
     // SW1-SW9 active, 9SWx15CS matrix